@@ -6,9 +6,37 @@
 use cooklang::error::SourceReport;
 use cooklang::model::Recipe;
 use cooklang::{Converter, CooklangParser, Extensions};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+// ============================================================================
+// Shopping list output types
+// ============================================================================
+
+#[derive(Serialize)]
+struct ShoppingListOutput {
+    categories: Vec<ShoppingListCategoryOutput>,
+    other: Vec<ShoppingListItemOutput>,
+}
+
+#[derive(Serialize)]
+struct ShoppingListCategoryOutput {
+    aisle: String,
+    items: Vec<ShoppingListItemOutput>,
+}
+
+#[derive(Serialize)]
+struct ShoppingListItemOutput {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quantity: Option<QuantityOutput>,
+    sources: Vec<String>,
+    /// `false` when this line could not be merged with others of the same
+    /// name (text quantity, missing quantity, or an unconvertible unit) and
+    /// is kept as its own un-summed entry instead.
+    summed: bool,
+}
+
 rustler::init!("Elixir.CooklangEx.Native");
 
 // ============================================================================
@@ -17,7 +45,7 @@ rustler::init!("Elixir.CooklangEx.Native");
 
 #[derive(Serialize)]
 struct RecipeOutput {
-    metadata: HashMap<String, String>,
+    metadata: MetadataOutput,
     ingredients: Vec<IngredientOutput>,
     cookware: Vec<CookwareOutput>,
     timers: Vec<TimerOutput>,
@@ -25,6 +53,31 @@ struct RecipeOutput {
     warnings: Vec<String>,
 }
 
+/// The recipe's YAML front-matter, preserved in its original shape (arrays
+/// stay arrays, numbers stay numbers) under `raw`, plus the handful of keys
+/// the parser gives special meaning so callers don't have to re-parse them.
+#[derive(Serialize)]
+struct MetadataOutput {
+    raw: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    servings: Option<serde_json::Value>,
+    /// Kept as a `Value` (not flattened to `String`) because it, like
+    /// `author`, can legitimately be a list or nested mapping (e.g. a
+    /// multi-value author block) rather than a bare scalar.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    time: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    course: Option<serde_json::Value>,
+}
+
 #[derive(Serialize)]
 struct IngredientOutput {
     name: String,
@@ -32,6 +85,21 @@ struct IngredientOutput {
     quantity: Option<QuantityOutput>,
     #[serde(skip_serializing_if = "Option::is_none")]
     note: Option<String>,
+    /// Marked with `?` in the recipe, e.g. `@?salt{}`.
+    optional: bool,
+    /// Marked with `-`, meaning it's not included in the ingredient list.
+    hidden: bool,
+    /// Marked with `@`... `{}` referencing another recipe rather than a
+    /// plain ingredient.
+    recipe_reference: bool,
+    /// Set when this entry is a "same as" reference (`&`) to an earlier
+    /// ingredient; the index is into the recipe's `ingredients` list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    references_to: Option<usize>,
+    /// Indices of ingredients that reference this one via "same as", so
+    /// consumers can collapse them into a single shopping/ingredient line.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    referenced_by: Vec<usize>,
 }
 
 #[derive(Serialize)]
@@ -90,6 +158,8 @@ enum ItemOutput {
     Cookware { index: usize },
     #[serde(rename = "timer")]
     Timer { index: usize },
+    #[serde(rename = "inline_quantity")]
+    InlineQuantity { index: usize, text: String },
 }
 
 // ============================================================================
@@ -184,30 +254,584 @@ fn parse_aisle_config(input: &str) -> Result<String, String> {
     }
 }
 
+/// Convert a parsed Cooklang recipe into a schema.org `Recipe` JSON-LD
+/// object, so it can be published on the web or fed to cookbook importers.
+///
+/// Returns `{:ok, json_string}` on success or `{:error, message}` on failure.
+#[rustler::nif(schedule = "DirtyCpu")]
+fn to_schema_org(input: &str, all_extensions: bool) -> Result<String, String> {
+    let extensions = if all_extensions {
+        Extensions::all()
+    } else {
+        Extensions::empty()
+    };
+
+    let parser = CooklangParser::new(extensions, Converter::default());
+
+    match parser.parse(input).into_result() {
+        Ok((recipe, _report)) => {
+            let output = convert_to_schema_org(&recipe);
+            serde_json::to_string(&output).map_err(|e| format!("JSON serialization error: {}", e))
+        }
+        Err(report) => Err(format_errors(&report)),
+    }
+}
+
+/// Parse a recipe and re-express every ingredient, cookware, and timer
+/// quantity in the requested unit system ("metric" or "imperial").
+///
+/// Non-convertible (`Value::Text`) quantities, and quantities whose unit has
+/// no best-fit match in the target system, are left untouched. Returns the
+/// same `RecipeOutput` shape as [`parse`] so a frontend can offer a
+/// one-click "show in metric" toggle without re-parsing.
+#[rustler::nif(schedule = "DirtyCpu")]
+fn parse_and_convert(input: &str, target_system: &str, all_extensions: bool) -> Result<String, String> {
+    if target_system != "metric" && target_system != "imperial" {
+        return Err(format!(
+            "unknown target_system {:?}, expected \"metric\" or \"imperial\"",
+            target_system
+        ));
+    }
+
+    let extensions = if all_extensions {
+        Extensions::all()
+    } else {
+        Extensions::empty()
+    };
+
+    let parser = CooklangParser::new(extensions, Converter::default());
+
+    match parser.parse(input).into_result() {
+        Ok((recipe, report)) => {
+            let converter = parser.converter();
+            let output = convert_recipe_with(&recipe, &report, |q| {
+                convert_quantity_to_system(q, target_system, converter)
+            });
+            serde_json::to_string(&output).map_err(|e| format!("JSON serialization error: {}", e))
+        }
+        Err(report) => Err(format_errors(&report)),
+    }
+}
+
+#[derive(Deserialize)]
+struct ParseManyRequest {
+    id: serde_json::Value,
+    source: String,
+}
+
+/// Parse a batch of recipes given as newline-delimited JSON, one
+/// `{"id": ..., "source": "..."}` record per line.
+///
+/// Builds a single `CooklangParser` and reuses it for every line, returning
+/// one ndjson result line per input: `{"id": ..., "ok": <recipe_json>}` on
+/// success or `{"id": ..., "error": "..."}` on failure. This lets a caller
+/// parse a whole cookbook in one NIF round-trip instead of paying the
+/// BEAM/NIF boundary cost per recipe.
+#[rustler::nif(schedule = "DirtyCpu")]
+fn parse_many(ndjson_input: &str, all_extensions: bool) -> Result<String, String> {
+    let extensions = if all_extensions {
+        Extensions::all()
+    } else {
+        Extensions::empty()
+    };
+
+    let parser = CooklangParser::new(extensions, Converter::default());
+    let mut out = String::new();
+
+    for line in ndjson_input.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let result_line = match serde_json::from_str::<ParseManyRequest>(line) {
+            Ok(req) => match parser.parse(&req.source).into_result() {
+                Ok((recipe, report)) => {
+                    let output = convert_recipe(&recipe, &report);
+                    serde_json::json!({ "id": req.id, "ok": output })
+                }
+                Err(report) => serde_json::json!({ "id": req.id, "error": format_errors(&report) }),
+            },
+            Err(e) => serde_json::json!({ "id": serde_json::Value::Null, "error": format!("invalid ndjson record: {}", e) }),
+        };
+
+        out.push_str(
+            &serde_json::to_string(&result_line)
+                .map_err(|e| format!("JSON serialization error: {}", e))?,
+        );
+        out.push('\n');
+    }
+
+    Ok(out)
+}
+
+/// Parse several Cooklang recipes and aggregate their ingredients into a
+/// single shopping list, grouped by aisle using an `cooklang::aisle` config.
+///
+/// `recipes` is a list of `(recipe_name, source)` pairs. Ingredients with the
+/// same (case-insensitively normalized) name are merged: matching or
+/// convertible numeric quantities are summed, while text or missing
+/// quantities are kept as separate lines so nothing is silently dropped.
+/// Returns `{:ok, json_string}` on success or `{:error, message}` on failure.
+#[rustler::nif(schedule = "DirtyCpu")]
+fn shopping_list(
+    recipes: Vec<(String, String)>,
+    aisle_config: &str,
+    all_extensions: bool,
+) -> Result<String, String> {
+    let extensions = if all_extensions {
+        Extensions::all()
+    } else {
+        Extensions::empty()
+    };
+
+    let parser = CooklangParser::new(extensions, Converter::default());
+    let aisle_conf = cooklang::aisle::parse(aisle_config).map_err(|e| e.to_string())?;
+
+    let mut aggregated: HashMap<String, AggregatedIngredient> = HashMap::new();
+    // Preserve first-seen order so the output is stable across runs.
+    let mut order: Vec<String> = Vec::new();
+
+    for (recipe_name, source) in &recipes {
+        let (recipe, _report) = parser
+            .parse(source)
+            .into_result()
+            .map_err(|report| format!("{}: {}", recipe_name, format_errors(&report)))?;
+
+        for ing in &recipe.ingredients {
+            let key = ing.name.trim().to_lowercase();
+            if !aggregated.contains_key(&key) {
+                order.push(key.clone());
+            }
+            let entry = aggregated
+                .entry(key)
+                .or_insert_with(|| AggregatedIngredient::new(ing.name.trim().to_string()));
+            entry.add(ing.quantity.as_ref(), recipe_name, parser.converter());
+        }
+    }
+
+    let mut categories: Vec<ShoppingListCategoryOutput> = Vec::new();
+    let mut other: Vec<ShoppingListItemOutput> = Vec::new();
+
+    for key in order {
+        let entry = aggregated.remove(&key).expect("just inserted");
+        let aisle = aisle_conf.category_for(&entry.name).map(|c| c.to_string());
+        let items = entry.into_items();
+
+        match aisle {
+            Some(aisle_name) => {
+                if let Some(category) = categories.iter_mut().find(|c| c.aisle == aisle_name) {
+                    category.items.extend(items);
+                } else {
+                    categories.push(ShoppingListCategoryOutput {
+                        aisle: aisle_name,
+                        items,
+                    });
+                }
+            }
+            None => other.extend(items),
+        }
+    }
+
+    let output = ShoppingListOutput { categories, other };
+    serde_json::to_string(&output).map_err(|e| format!("JSON serialization error: {}", e))
+}
+
 // ============================================================================
-// Conversion helpers
+// Shopping list aggregation helpers
 // ============================================================================
 
-fn convert_recipe(recipe: &Recipe, report: &SourceReport) -> RecipeOutput {
-    let metadata: HashMap<String, String> = recipe
-        .metadata
-        .map
+/// Accumulates every recipe's contribution to a single (normalized)
+/// ingredient name before it is flattened into output lines.
+struct AggregatedIngredient {
+    name: String,
+    /// `true` once a numeric quantity has contributed to `summed_amount`, so
+    /// an unsummed unit (`None`) can be told apart from "nothing summed yet".
+    started: bool,
+    summed_amount: Option<f64>,
+    summed_unit: Option<String>,
+    summed_sources: Vec<String>,
+    unsummed: Vec<(Option<QuantityOutput>, String)>,
+}
+
+impl AggregatedIngredient {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            started: false,
+            summed_amount: None,
+            summed_unit: None,
+            summed_sources: Vec::new(),
+            unsummed: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, quantity: Option<&cooklang::Quantity>, source: &str, converter: &Converter) {
+        let Some(quantity) = quantity else {
+            self.unsummed.push((None, source.to_string()));
+            return;
+        };
+
+        let amount = match quantity.value() {
+            cooklang::Value::Number(n) => n.value(),
+            _ => {
+                self.unsummed
+                    .push((Some(convert_quantity(quantity)), source.to_string()));
+                return;
+            }
+        };
+        let unit = quantity.unit().map(|s| s.to_string());
+
+        if !self.started {
+            self.started = true;
+            self.summed_amount = Some(amount);
+            self.summed_unit = unit;
+            self.summed_sources.push(source.to_string());
+            return;
+        }
+
+        match (&self.summed_unit, &unit) {
+            (None, None) => {
+                *self.summed_amount.get_or_insert(0.0) += amount;
+                self.summed_sources.push(source.to_string());
+            }
+            (Some(current), Some(incoming)) if current.eq_ignore_ascii_case(incoming) => {
+                *self.summed_amount.get_or_insert(0.0) += amount;
+                self.summed_sources.push(source.to_string());
+            }
+            (Some(current), Some(incoming)) => {
+                match converter.convert(amount, incoming, current) {
+                    Ok(converted) => {
+                        *self.summed_amount.get_or_insert(0.0) += converted;
+                        self.summed_sources.push(source.to_string());
+                    }
+                    Err(_) => self.unsummed.push((
+                        Some(convert_quantity(quantity)),
+                        source.to_string(),
+                    )),
+                }
+            }
+            (None, Some(_)) | (Some(_), None) => self.unsummed.push((
+                Some(convert_quantity(quantity)),
+                source.to_string(),
+            )),
+        }
+    }
+
+    fn into_items(self) -> Vec<ShoppingListItemOutput> {
+        let mut items = Vec::new();
+
+        if let Some(amount) = self.summed_amount {
+            items.push(ShoppingListItemOutput {
+                name: self.name.clone(),
+                quantity: Some(QuantityOutput {
+                    value: Some(ValueOutput::Number(amount)),
+                    unit: self.summed_unit,
+                }),
+                sources: self.summed_sources,
+                summed: true,
+            });
+        }
+
+        for (quantity, source) in self.unsummed {
+            items.push(ShoppingListItemOutput {
+                name: self.name.clone(),
+                quantity,
+                sources: vec![source],
+                summed: false,
+            });
+        }
+
+        items
+    }
+}
+
+fn format_errors(report: &SourceReport) -> String {
+    report
+        .errors()
+        .map(|e| e.message.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// ============================================================================
+// schema.org export helpers
+// ============================================================================
+
+fn convert_to_schema_org(recipe: &Recipe) -> serde_json::Value {
+    let metadata = build_metadata(recipe);
+    let get = |key: &str| -> Option<String> {
+        metadata
+            .raw
+            .get(key)
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+    };
+
+    let recipe_ingredient: Vec<String> = recipe.ingredients.iter().map(render_ingredient_line).collect();
+
+    let recipe_instructions: Vec<serde_json::Value> = recipe
+        .sections
         .iter()
-        .map(|(k, v)| {
-            let key = k.as_str().unwrap_or("").to_string();
-            let value = v.as_str().unwrap_or("").to_string();
-            (key, value)
+        .flat_map(|section| section.content.iter())
+        .filter_map(|item| match item {
+            cooklang::Content::Step(step) => Some(serde_json::json!({
+                "@type": "HowToStep",
+                "text": render_step_text(step, recipe),
+            })),
+            _ => None,
         })
         .collect();
 
+    let total_minutes: f64 = recipe
+        .timers
+        .iter()
+        .filter_map(|t| t.quantity.as_ref())
+        .filter_map(quantity_minutes)
+        .sum();
+
+    let mut schema = serde_json::json!({
+        "@context": "https://schema.org",
+        "@type": "Recipe",
+        "recipeIngredient": recipe_ingredient,
+        "recipeInstructions": recipe_instructions,
+    });
+
+    let obj = schema.as_object_mut().expect("schema is an object");
+
+    if let Some(servings) = &metadata.servings {
+        let servings = match servings {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        obj.insert("recipeYield".to_string(), serde_json::Value::String(servings));
+    }
+    if let Some(author) = &metadata.author {
+        // schema.org properties accept a single value or a list, so a
+        // multi-value author block (a YAML array) is passed through as-is
+        // rather than being flattened to a single string.
+        obj.insert("author".to_string(), author.clone());
+    }
+    if !metadata.tags.is_empty() {
+        obj.insert(
+            "keywords".to_string(),
+            serde_json::Value::String(metadata.tags.join(", ")),
+        );
+    }
+
+    let prep_time = get("prep time").and_then(|s| parse_duration_minutes(&s));
+    let cook_time = get("cook time").and_then(|s| parse_duration_minutes(&s));
+    let total_time = get("time")
+        .and_then(|s| parse_duration_minutes(&s))
+        .or_else(|| match (prep_time, cook_time) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or(0.0) + b.unwrap_or(0.0)),
+        })
+        .or(if total_minutes > 0.0 { Some(total_minutes) } else { None });
+
+    if let Some(minutes) = prep_time {
+        obj.insert(
+            "prepTime".to_string(),
+            serde_json::Value::String(iso8601_duration(minutes)),
+        );
+    }
+    if let Some(minutes) = cook_time {
+        obj.insert(
+            "cookTime".to_string(),
+            serde_json::Value::String(iso8601_duration(minutes)),
+        );
+    }
+    if let Some(minutes) = total_time {
+        obj.insert(
+            "totalTime".to_string(),
+            serde_json::Value::String(iso8601_duration(minutes)),
+        );
+    }
+
+    schema
+}
+
+fn render_ingredient_line(ing: &cooklang::Ingredient) -> String {
+    match ing.quantity.as_ref() {
+        Some(q) => format!("{} {}", render_quantity_text(q), ing.name).trim().to_string(),
+        None => ing.name.clone(),
+    }
+}
+
+fn render_quantity_text(q: &cooklang::Quantity) -> String {
+    let value = match q.value() {
+        cooklang::Value::Number(n) => n.value().to_string(),
+        cooklang::Value::Range { start, end } => format!("{}-{}", start.value(), end.value()),
+        cooklang::Value::Text(t) => t.clone(),
+    };
+    match q.unit() {
+        Some(unit) => format!("{} {}", value, unit),
+        None => value,
+    }
+}
+
+fn render_step_text(step: &cooklang::Step, recipe: &Recipe) -> String {
+    step.items
+        .iter()
+        .map(|item| match item {
+            cooklang::Item::Text { value } => value.to_string(),
+            cooklang::Item::Ingredient { index } => recipe.ingredients[*index].name.clone(),
+            cooklang::Item::Cookware { index } => recipe.cookware[*index].name.clone(),
+            cooklang::Item::Timer { index } => recipe.timers[*index]
+                .quantity
+                .as_ref()
+                .map(render_quantity_text)
+                .unwrap_or_default(),
+            cooklang::Item::InlineQuantity { index } => render_inline_quantity(recipe, *index),
+        })
+        .collect::<Vec<_>>()
+        .join("")
+}
+
+/// Render a standalone inline quantity token's text (e.g. a bare `{2%kg}`
+/// in a step that isn't tied to an ingredient/cookware/timer).
+///
+/// The index points into `recipe.inline_quantities`; it is looked up
+/// bounds-checked, so an out-of-range index renders as an empty string
+/// instead of panicking the NIF call.
+fn render_inline_quantity(recipe: &Recipe, index: usize) -> String {
+    recipe
+        .inline_quantities
+        .get(index)
+        .map(render_quantity_text)
+        .unwrap_or_default()
+}
+
+fn quantity_minutes(q: &cooklang::Quantity) -> Option<f64> {
+    let amount = match q.value() {
+        cooklang::Value::Number(n) => n.value(),
+        _ => return None,
+    };
+    match q.unit()?.to_lowercase().as_str() {
+        "s" | "sec" | "secs" | "second" | "seconds" => Some(amount / 60.0),
+        "min" | "mins" | "minute" | "minutes" => Some(amount),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Some(amount * 60.0),
+        _ => None,
+    }
+}
+
+/// Best-effort parse of a free-form metadata duration ("1 hour 30 minutes",
+/// "90 minutes", "45") into a number of minutes.
+fn parse_duration_minutes(text: &str) -> Option<f64> {
+    if let Ok(minutes) = text.trim().parse::<f64>() {
+        return Some(minutes);
+    }
+
+    let mut total = 0.0;
+    let mut found = false;
+    let mut chars = text.split_whitespace().peekable();
+    while let Some(word) = chars.next() {
+        if let Ok(n) = word.parse::<f64>() {
+            if let Some(unit) = chars.peek() {
+                let unit = unit.to_lowercase();
+                if unit.starts_with("hour") || unit == "h" || unit == "hr" || unit == "hrs" {
+                    total += n * 60.0;
+                    found = true;
+                    chars.next();
+                } else if unit.starts_with("min") {
+                    total += n;
+                    found = true;
+                    chars.next();
+                }
+            }
+        }
+    }
+
+    found.then_some(total)
+}
+
+fn iso8601_duration(total_minutes: f64) -> String {
+    let total_minutes = total_minutes.round() as i64;
+    let hours = total_minutes / 60;
+    let minutes = total_minutes % 60;
+
+    match (hours, minutes) {
+        (0, m) => format!("PT{}M", m),
+        (h, 0) => format!("PT{}H", h),
+        (h, m) => format!("PT{}H{}M", h, m),
+    }
+}
+
+// ============================================================================
+// Metadata helpers
+// ============================================================================
+
+/// Build the output metadata, keeping the original YAML shape under `raw`
+/// and additionally surfacing the keys the parser treats specially.
+fn build_metadata(recipe: &Recipe) -> MetadataOutput {
+    let mut raw = serde_json::Map::new();
+    for (k, v) in recipe.metadata.map.iter() {
+        let key = k.as_str().unwrap_or_default().to_string();
+        let value = serde_json::to_value(v).unwrap_or(serde_json::Value::Null);
+        raw.insert(key, value);
+    }
+
+    let servings = raw.get("servings").cloned();
+    let time = raw.get("time").cloned();
+    let title = raw.get("title").cloned();
+    let author = raw.get("author").cloned();
+    let source = raw.get("source").cloned();
+    let course = raw.get("course").cloned();
+    let tags = raw
+        .get("tags")
+        .or_else(|| raw.get("keywords"))
+        .map(value_to_string_list)
+        .unwrap_or_default();
+
+    MetadataOutput {
+        raw: serde_json::Value::Object(raw),
+        servings,
+        title,
+        author,
+        source,
+        time,
+        tags,
+        course,
+    }
+}
+
+fn value_to_string_list(value: &serde_json::Value) -> Vec<String> {
+    match value {
+        serde_json::Value::Array(items) => items
+            .iter()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect(),
+        serde_json::Value::String(s) => s
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+// ============================================================================
+// Conversion helpers
+// ============================================================================
+
+fn convert_recipe(recipe: &Recipe, report: &SourceReport) -> RecipeOutput {
+    convert_recipe_with(recipe, report, convert_quantity)
+}
+
+/// Like [`convert_recipe`], but quantities are run through `quantize` first,
+/// so callers can e.g. re-express them in a different unit system.
+fn convert_recipe_with(
+    recipe: &Recipe,
+    report: &SourceReport,
+    quantize: impl Fn(&cooklang::Quantity) -> QuantityOutput,
+) -> RecipeOutput {
+    let metadata = build_metadata(recipe);
+
     let ingredients: Vec<IngredientOutput> = recipe
         .ingredients
         .iter()
-        .map(|ing| IngredientOutput {
-            name: ing.name.clone(),
-            quantity: ing.quantity.as_ref().map(convert_quantity),
-            note: ing.note.clone(),
-        })
+        .map(|ing| convert_ingredient(ing, &quantize))
         .collect();
 
     let cookware: Vec<CookwareOutput> = recipe
@@ -215,7 +839,7 @@ fn convert_recipe(recipe: &Recipe, report: &SourceReport) -> RecipeOutput {
         .iter()
         .map(|cw| CookwareOutput {
             name: cw.name.clone(),
-            quantity: cw.quantity.as_ref().map(convert_quantity),
+            quantity: cw.quantity.as_ref().map(&quantize),
             note: cw.note.clone(),
         })
         .collect();
@@ -225,7 +849,7 @@ fn convert_recipe(recipe: &Recipe, report: &SourceReport) -> RecipeOutput {
         .iter()
         .map(|t| TimerOutput {
             name: t.name.clone(),
-            quantity: t.quantity.as_ref().map(convert_quantity),
+            quantity: t.quantity.as_ref().map(&quantize),
         })
         .collect();
 
@@ -239,7 +863,7 @@ fn convert_recipe(recipe: &Recipe, report: &SourceReport) -> RecipeOutput {
                 .iter()
                 .filter_map(|item| {
                     if let cooklang::Content::Step(step) = item {
-                        Some(convert_step(&step))
+                        Some(convert_step(step, recipe))
                     } else {
                         None
                     }
@@ -276,7 +900,131 @@ fn convert_quantity(q: &cooklang::Quantity) -> QuantityOutput {
     }
 }
 
-fn convert_step(step: &cooklang::Step) -> StepOutput {
+/// Re-express a numeric quantity's unit in `target_system`, converting the
+/// value through `converter`. Falls back to [`convert_quantity`] unchanged
+/// when there's no unit, no best-fit target unit, or the conversion fails.
+fn convert_quantity_to_system(
+    q: &cooklang::Quantity,
+    target_system: &str,
+    converter: &Converter,
+) -> QuantityOutput {
+    let (amount, unit) = match (q.value(), q.unit()) {
+        (cooklang::Value::Number(n), Some(unit)) => (n.value(), unit),
+        _ => return convert_quantity(q),
+    };
+
+    let Some(category) = unit_category(unit) else {
+        return convert_quantity(q);
+    };
+    let reference_unit = reference_unit(target_system, category);
+
+    // Convert into the smallest unit of the target system's ladder first,
+    // purely to judge magnitude; the actual numbers still come from
+    // `converter`, not from our own approximate ratios.
+    let reference_amount = match converter.convert(amount, unit, reference_unit) {
+        Ok(v) => v,
+        Err(_) => return convert_quantity(q),
+    };
+
+    let display_unit = best_fit_unit(target_system, category, reference_amount);
+
+    let (display_unit, display_amount) = if display_unit == reference_unit {
+        (reference_unit, reference_amount)
+    } else {
+        match converter.convert(amount, unit, display_unit) {
+            Ok(v) => (display_unit, v),
+            Err(_) => (reference_unit, reference_amount),
+        }
+    };
+
+    QuantityOutput {
+        value: Some(ValueOutput::Number(display_amount)),
+        unit: Some(display_unit.to_string()),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum UnitCategory {
+    Mass,
+    Volume,
+}
+
+/// Which of the two convertible categories `unit` belongs to, or `None` if
+/// it's not one we know how to re-express in another unit system.
+fn unit_category(unit: &str) -> Option<UnitCategory> {
+    match unit.to_lowercase().as_str() {
+        "g" | "gram" | "grams" | "kg" | "kilogram" | "kilograms" | "oz" | "ounce" | "ounces"
+        | "lb" | "lbs" | "pound" | "pounds" | "stone" | "stones" => Some(UnitCategory::Mass),
+        "ml" | "milliliter" | "milliliters" | "millilitre" | "millilitres" | "l" | "liter"
+        | "liters" | "litre" | "litres" | "tsp" | "teaspoon" | "teaspoons" | "tbsp"
+        | "tablespoon" | "tablespoons" | "cup" | "cups" | "pint" | "pints" | "quart"
+        | "quarts" | "gallon" | "gallons" | "fl oz" | "floz" | "fluid ounce" | "fluid ounces" => {
+            Some(UnitCategory::Volume)
+        }
+        _ => None,
+    }
+}
+
+/// The smallest unit in `target_system`'s ladder for `category`, used as
+/// the common unit to measure magnitude against.
+fn reference_unit(target_system: &str, category: UnitCategory) -> &'static str {
+    match (target_system, category) {
+        ("metric", UnitCategory::Mass) => "g",
+        ("metric", UnitCategory::Volume) => "ml",
+        (_, UnitCategory::Mass) => "oz",
+        (_, UnitCategory::Volume) => "tsp",
+    }
+}
+
+/// The best-fit unit to display a quantity in, given its magnitude
+/// expressed in `reference_unit(target_system, category)`.
+fn best_fit_unit(target_system: &str, category: UnitCategory, reference_amount: f64) -> &'static str {
+    let reference_amount = reference_amount.abs();
+    match (target_system, category) {
+        ("metric", UnitCategory::Mass) => {
+            if reference_amount >= 1000.0 {
+                "kg"
+            } else {
+                "g"
+            }
+        }
+        ("metric", UnitCategory::Volume) => {
+            if reference_amount >= 1000.0 {
+                "l"
+            } else {
+                "ml"
+            }
+        }
+        (_, UnitCategory::Mass) => {
+            if reference_amount >= 224.0 {
+                "stone"
+            } else if reference_amount >= 16.0 {
+                "lb"
+            } else {
+                "oz"
+            }
+        }
+        (_, UnitCategory::Volume) => {
+            if reference_amount >= 768.0 {
+                "gallon"
+            } else if reference_amount >= 192.0 {
+                "quart"
+            } else if reference_amount >= 96.0 {
+                "pint"
+            } else if reference_amount >= 48.0 {
+                "cup"
+            } else if reference_amount >= 6.0 {
+                "fl oz"
+            } else if reference_amount >= 3.0 {
+                "tbsp"
+            } else {
+                "tsp"
+            }
+        }
+    }
+}
+
+fn convert_step(step: &cooklang::Step, recipe: &Recipe) -> StepOutput {
     let items: Vec<ItemOutput> = step
         .items
         .iter()
@@ -287,11 +1035,35 @@ fn convert_step(step: &cooklang::Step) -> StepOutput {
             cooklang::Item::Ingredient { index } => ItemOutput::Ingredient { index: *index },
             cooklang::Item::Cookware { index } => ItemOutput::Cookware { index: *index },
             cooklang::Item::Timer { index } => ItemOutput::Timer { index: *index },
-            cooklang::Item::InlineQuantity { index: _ } => ItemOutput::Text {
-                value: String::new(),
+            cooklang::Item::InlineQuantity { index } => ItemOutput::InlineQuantity {
+                index: *index,
+                text: render_inline_quantity(recipe, *index),
             },
         })
         .collect();
 
     StepOutput { items }
 }
+
+/// Build an [`IngredientOutput`], surfacing its modifiers (optional, hidden,
+/// recipe reference) and "same as" relation to another ingredient.
+fn convert_ingredient(
+    ing: &cooklang::Ingredient,
+    quantize: impl Fn(&cooklang::Quantity) -> QuantityOutput,
+) -> IngredientOutput {
+    let (references_to, referenced_by) = match &ing.relation {
+        cooklang::IngredientRelation::Reference { references_to } => (Some(*references_to), Vec::new()),
+        cooklang::IngredientRelation::Definition { referenced_from } => (None, referenced_from.clone()),
+    };
+
+    IngredientOutput {
+        name: ing.name.clone(),
+        quantity: ing.quantity.as_ref().map(quantize),
+        note: ing.note.clone(),
+        optional: ing.modifiers.contains(cooklang::Modifiers::OPT),
+        hidden: ing.modifiers.contains(cooklang::Modifiers::HIDDEN),
+        recipe_reference: ing.modifiers.contains(cooklang::Modifiers::RECIPE),
+        references_to,
+        referenced_by,
+    }
+}